@@ -0,0 +1,152 @@
+use crate::decode::{RegisterType, WordOrder};
+use anyhow::Result;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::path::Path;
+
+/// The shared connection a batch `run` opens once before issuing its reads.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum TransportConfig {
+    Serial {
+        port: String,
+        #[serde(default = "default_baud")]
+        baud: u32,
+        #[serde(default = "default_data_bits")]
+        data_bits: u8,
+        #[serde(default = "default_stop_bits")]
+        stop_bits: u8,
+        #[serde(default = "default_parity")]
+        parity: String,
+        #[serde(default = "default_timeout")]
+        timeout: u64,
+    },
+    Tcp {
+        host: String,
+        #[serde(default = "default_tcp_port")]
+        port: u16,
+        #[serde(default = "default_timeout")]
+        timeout: u64,
+    },
+}
+
+fn default_baud() -> u32 {
+    9600
+}
+
+fn default_data_bits() -> u8 {
+    8
+}
+
+fn default_stop_bits() -> u8 {
+    1
+}
+
+fn default_parity() -> String {
+    "none".to_string()
+}
+
+fn default_timeout() -> u64 {
+    1000
+}
+
+fn default_tcp_port() -> u16 {
+    502
+}
+
+fn default_function_code() -> u8 {
+    3
+}
+
+fn default_scale() -> Decimal {
+    Decimal::ONE
+}
+
+/// One named read in a batch config: the slave, function, address range and decode
+/// options of `Read`/`ReadTcp`. Polling (`--poll`/`--diff`) has no counterpart here.
+#[derive(Deserialize)]
+pub struct ReadRequest {
+    pub name: String,
+    pub slave: u8,
+    pub address: u16,
+    pub count: u16,
+    #[serde(default = "default_function_code")]
+    pub function_code: u8,
+    #[serde(default, rename = "type")]
+    pub reg_type: Option<RegisterType>,
+    #[serde(default)]
+    pub word_order: WordOrder,
+    #[serde(default = "default_scale")]
+    pub scale: Decimal,
+    #[serde(default)]
+    pub offset: Decimal,
+}
+
+#[derive(Deserialize)]
+pub struct BatchConfig {
+    pub transport: TransportConfig,
+    pub reads: Vec<ReadRequest>,
+}
+
+pub fn load(path: &Path) -> Result<BatchConfig> {
+    let text = std::fs::read_to_string(path)?;
+    let config = serde_json::from_str(&text)?;
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_tcp_batch_with_defaults() {
+        let json = r#"{
+            "transport": { "type": "tcp", "host": "127.0.0.1" },
+            "reads": [
+                { "name": "status", "slave": 1, "address": 0, "count": 2 }
+            ]
+        }"#;
+        let dir = std::env::temp_dir();
+        let path = dir.join("modbus-cli-config-test-tcp.json");
+        std::fs::write(&path, json).unwrap();
+
+        let batch = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        match batch.transport {
+            TransportConfig::Tcp { host, port, .. } => {
+                assert_eq!(host, "127.0.0.1");
+                assert_eq!(port, 502);
+            }
+            TransportConfig::Serial { .. } => panic!("expected tcp transport"),
+        }
+        assert_eq!(batch.reads.len(), 1);
+        assert_eq!(batch.reads[0].function_code, 3);
+        assert_eq!(batch.reads[0].scale, Decimal::ONE);
+    }
+
+    #[test]
+    fn loads_serial_batch_with_explicit_fields() {
+        let json = r#"{
+            "transport": { "type": "serial", "port": "/dev/ttyUSB0", "baud": 19200 },
+            "reads": [
+                { "name": "temp", "slave": 2, "address": 10, "count": 2, "type": "f32", "scale": 0.1 }
+            ]
+        }"#;
+        let dir = std::env::temp_dir();
+        let path = dir.join("modbus-cli-config-test-serial.json");
+        std::fs::write(&path, json).unwrap();
+
+        let batch = load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        match batch.transport {
+            TransportConfig::Serial { port, baud, .. } => {
+                assert_eq!(port, "/dev/ttyUSB0");
+                assert_eq!(baud, 19200);
+            }
+            TransportConfig::Tcp { .. } => panic!("expected serial transport"),
+        }
+        assert!(matches!(batch.reads[0].reg_type, Some(RegisterType::F32)));
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::{anyhow, bail, Result};
+use clap::ValueEnum;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+/// The numeric or textual type a block of registers should be reinterpreted as.
+#[derive(Clone, Copy, Debug, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RegisterType {
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    String,
+}
+
+/// Register order for multi-register types (word swap).
+#[derive(Clone, Copy, Debug, Default, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WordOrder {
+    #[default]
+    Big,
+    Little,
+}
+
+impl RegisterType {
+    /// Number of 16-bit registers consumed by one value of this type.
+    /// `String` has no fixed width; it consumes the whole block as one value.
+    fn word_width(self) -> usize {
+        match self {
+            RegisterType::U16 | RegisterType::I16 => 1,
+            RegisterType::U32 | RegisterType::I32 | RegisterType::F32 => 2,
+            RegisterType::U64 | RegisterType::I64 | RegisterType::F64 => 4,
+            RegisterType::String => 0,
+        }
+    }
+}
+
+/// One decoded value plus the raw registers it was assembled from.
+pub struct DecodedGroup {
+    pub address: u16,
+    pub raw_words: Vec<u16>,
+    pub value: String,
+    /// The scaled numeric value, for everything except `RegisterType::String`.
+    pub numeric: Option<Decimal>,
+}
+
+/// Decode a slice of raw registers into typed, scaled values.
+///
+/// Numeric types are grouped into chunks of `reg_type`'s word width; `String`
+/// treats the whole slice as a single value. Returns an error if `registers`
+/// is not a multiple of the type's width.
+pub fn decode_registers(
+    registers: &[u16],
+    start_address: u16,
+    reg_type: RegisterType,
+    word_order: WordOrder,
+    scale: Decimal,
+    offset: Decimal,
+) -> Result<Vec<DecodedGroup>> {
+    if let RegisterType::String = reg_type {
+        return Ok(vec![DecodedGroup {
+            address: start_address,
+            raw_words: registers.to_vec(),
+            value: decode_string(registers),
+            numeric: None,
+        }]);
+    }
+
+    let width = reg_type.word_width();
+    if registers.is_empty() || registers.len() % width != 0 {
+        bail!(
+            "register count {} is not a multiple of the {:?} width ({})",
+            registers.len(),
+            reg_type,
+            width
+        );
+    }
+
+    registers
+        .chunks(width)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let address = start_address + (i * width) as u16;
+            let ordered: Vec<u16> = match word_order {
+                WordOrder::Big => chunk.to_vec(),
+                WordOrder::Little => chunk.iter().rev().copied().collect(),
+            };
+            let mut bytes = Vec::with_capacity(width * 2);
+            for word in &ordered {
+                bytes.extend_from_slice(&word.to_be_bytes());
+            }
+            let numeric = decode_numeric(reg_type, &bytes, scale, offset)?;
+            Ok(DecodedGroup {
+                address,
+                raw_words: chunk.to_vec(),
+                value: numeric.to_string(),
+                numeric: Some(numeric),
+            })
+        })
+        .collect()
+}
+
+fn decode_numeric(reg_type: RegisterType, bytes: &[u8], scale: Decimal, offset: Decimal) -> Result<Decimal> {
+    let raw: Decimal = match reg_type {
+        RegisterType::U16 => Decimal::from(u16::from_be_bytes(bytes.try_into()?)),
+        RegisterType::I16 => Decimal::from(i16::from_be_bytes(bytes.try_into()?)),
+        RegisterType::U32 => Decimal::from(u32::from_be_bytes(bytes.try_into()?)),
+        RegisterType::I32 => Decimal::from(i32::from_be_bytes(bytes.try_into()?)),
+        RegisterType::U64 => Decimal::from(u64::from_be_bytes(bytes.try_into()?)),
+        RegisterType::I64 => Decimal::from(i64::from_be_bytes(bytes.try_into()?)),
+        RegisterType::F32 => Decimal::try_from(f32::from_be_bytes(bytes.try_into()?) as f64)
+            .map_err(|e| anyhow!("could not represent value as decimal: {}", e))?,
+        RegisterType::F64 => Decimal::try_from(f64::from_be_bytes(bytes.try_into()?))
+            .map_err(|e| anyhow!("could not represent value as decimal: {}", e))?,
+        RegisterType::String => unreachable!("string is handled before chunking"),
+    };
+    Ok((raw * scale + offset).normalize())
+}
+
+fn decode_string(registers: &[u16]) -> String {
+    let mut bytes = Vec::with_capacity(registers.len() * 2);
+    for reg in registers {
+        let be = reg.to_be_bytes();
+        bytes.push(be[0]);
+        bytes.push(be[1]);
+    }
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_u16_big_endian() {
+        let groups = decode_registers(&[0x1234], 0, RegisterType::U16, WordOrder::Big, Decimal::ONE, Decimal::ZERO).unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].numeric, Some(Decimal::from(0x1234u16)));
+    }
+
+    #[test]
+    fn decode_u32_word_order_swap() {
+        // 0x0001_0002 assembled from registers [0x0001, 0x0002] in big-endian word order,
+        // and from [0x0002, 0x0001] in little-endian word order.
+        let big = decode_registers(&[0x0001, 0x0002], 0, RegisterType::U32, WordOrder::Big, Decimal::ONE, Decimal::ZERO).unwrap();
+        let little = decode_registers(&[0x0002, 0x0001], 0, RegisterType::U32, WordOrder::Little, Decimal::ONE, Decimal::ZERO).unwrap();
+        assert_eq!(big[0].numeric, Some(Decimal::from(0x0001_0002u32)));
+        assert_eq!(big[0].numeric, little[0].numeric);
+    }
+
+    #[test]
+    fn decode_applies_scale_and_offset() {
+        let groups = decode_registers(&[100], 0, RegisterType::U16, WordOrder::Big, Decimal::new(1, 1), Decimal::ONE).unwrap();
+        // 100 * 0.1 + 1 = 11
+        assert_eq!(groups[0].numeric, Some(Decimal::from(11)));
+    }
+
+    #[test]
+    fn decode_multiple_groups_advance_address() {
+        let groups = decode_registers(&[1, 2, 3, 4], 10, RegisterType::U16, WordOrder::Big, Decimal::ONE, Decimal::ZERO).unwrap();
+        let addresses: Vec<u16> = groups.iter().map(|g| g.address).collect();
+        assert_eq!(addresses, vec![10, 11, 12, 13]);
+    }
+
+    #[test]
+    fn decode_rejects_misaligned_register_count() {
+        match decode_registers(&[1, 2, 3], 0, RegisterType::U32, WordOrder::Big, Decimal::ONE, Decimal::ZERO) {
+            Err(e) => assert!(e.to_string().contains("not a multiple")),
+            Ok(_) => panic!("expected an error for a misaligned register count"),
+        }
+    }
+
+    #[test]
+    fn decode_string_trims_trailing_nul() {
+        // "AB" followed by a NUL-padded register.
+        let groups = decode_registers(&[0x4142, 0x0000], 0, RegisterType::String, WordOrder::Big, Decimal::ONE, Decimal::ZERO).unwrap();
+        assert_eq!(groups[0].value, "AB");
+        assert_eq!(groups[0].numeric, None);
+    }
+}
@@ -1,13 +1,40 @@
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use rust_decimal::Decimal;
+use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::time::Duration;
+use tokio_modbus::client::Context;
 use tokio_modbus::prelude::*;
 use tokio_serial::SerialStream;
 
+mod decode;
+use decode::{decode_registers, DecodedGroup, RegisterType, WordOrder};
+
+mod mqtt;
+use rust_decimal::prelude::ToPrimitive;
+
+mod output;
+use output::OutputFormat;
+
+mod config;
+
+/// Typed-decoding knobs shared by every read path (one-shot and polling).
+struct DecodeOptions {
+    reg_type: Option<RegisterType>,
+    word_order: WordOrder,
+    scale: Decimal,
+    offset: Decimal,
+}
+
 #[derive(Parser)]
 #[command(name = "modbus-cli")]
-#[command(about = "A CLI tool for Modbus serial communication")]
+#[command(about = "A CLI tool for Modbus serial (RTU) and TCP communication")]
 struct Cli {
+    /// Output format for read results
+    #[arg(long, value_enum, global = true, default_value = "table")]
+    format: OutputFormat,
+
     #[command(subcommand)]
     command: Commands,
 }
@@ -53,90 +80,1141 @@ enum Commands {
         /// Modbus function code (1=coils, 2=discrete_inputs, 3=holding_registers, 4=input_registers)
         #[arg(short, long, default_value = "3")]
         function_code: u8,
-        
+
+        /// Timeout in milliseconds
+        #[arg(long, default_value = "1000")]
+        timeout: u64,
+
+        /// Also decode holding/input registers as this type, shown alongside the raw u16 words
+        #[arg(long, value_enum)]
+        r#type: Option<RegisterType>,
+
+        /// Register order for multi-register types
+        #[arg(long, value_enum, default_value = "big")]
+        word_order: WordOrder,
+
+        /// Scale factor applied to decoded numeric values (value * scale + offset)
+        #[arg(long, default_value = "1")]
+        scale: Decimal,
+
+        /// Offset added after scaling
+        #[arg(long, default_value = "0")]
+        offset: Decimal,
+
+        /// Re-issue this read every <poll> milliseconds until Ctrl-C instead of reading once
+        #[arg(long)]
+        poll: Option<u64>,
+
+        /// With --poll, only print rows whose value changed since the previous poll
+        #[arg(long)]
+        diff: bool,
+    },
+    /// Read data from a Modbus TCP device
+    ReadTcp {
+        /// Device host name or IP address
+        #[arg(long)]
+        host: String,
+
+        /// Device port
+        #[arg(long, default_value = "502")]
+        port: u16,
+
+        /// Modbus slave address
+        #[arg(short, long)]
+        slave: u8,
+
+        /// Starting address to read from
+        #[arg(short, long)]
+        address: u16,
+
+        /// Number of registers to read
+        #[arg(short, long)]
+        count: u16,
+
+        /// Modbus function code (1=coils, 2=discrete_inputs, 3=holding_registers, 4=input_registers)
+        #[arg(short, long, default_value = "3")]
+        function_code: u8,
+
+        /// Timeout in milliseconds
+        #[arg(long, default_value = "1000")]
+        timeout: u64,
+
+        /// Also decode holding/input registers as this type, shown alongside the raw u16 words
+        #[arg(long, value_enum)]
+        r#type: Option<RegisterType>,
+
+        /// Register order for multi-register types
+        #[arg(long, value_enum, default_value = "big")]
+        word_order: WordOrder,
+
+        /// Scale factor applied to decoded numeric values (value * scale + offset)
+        #[arg(long, default_value = "1")]
+        scale: Decimal,
+
+        /// Offset added after scaling
+        #[arg(long, default_value = "0")]
+        offset: Decimal,
+
+        /// Re-issue this read every <poll> milliseconds until Ctrl-C instead of reading once
+        #[arg(long)]
+        poll: Option<u64>,
+
+        /// With --poll, only print rows whose value changed since the previous poll
+        #[arg(long)]
+        diff: bool,
+    },
+    /// Write data to a Modbus device
+    Write {
+        /// Serial port path (e.g., COM1, /dev/ttyUSB0)
+        #[arg(short, long)]
+        port: String,
+
+        /// Baud rate
+        #[arg(short, long, default_value = "9600")]
+        baud: u32,
+
+        /// Data bits (5, 6, 7, 8)
+        #[arg(long, default_value = "8")]
+        data_bits: u8,
+
+        /// Stop bits (1, 2)
+        #[arg(long, default_value = "1")]
+        stop_bits: u8,
+
+        /// Parity (none, odd, even)
+        #[arg(long, default_value = "none")]
+        parity: String,
+
+        /// Modbus slave address
+        #[arg(short, long)]
+        slave: u8,
+
+        /// Starting address to write to
+        #[arg(short, long)]
+        address: u16,
+
+        /// Modbus function code (5=write_single_coil, 6=write_single_register, 15=write_multiple_coils, 16=write_multiple_registers)
+        #[arg(short, long, default_value = "6")]
+        function_code: u8,
+
+        /// Comma-separated values to write (booleans for coils, decimal or 0x-prefixed hex for registers)
+        #[arg(long, value_delimiter = ',')]
+        values: Vec<String>,
+
+        /// Timeout in milliseconds
+        #[arg(long, default_value = "1000")]
+        timeout: u64,
+    },
+    /// Write data to a Modbus TCP device
+    WriteTcp {
+        /// Device host name or IP address
+        #[arg(long)]
+        host: String,
+
+        /// Device port
+        #[arg(long, default_value = "502")]
+        port: u16,
+
+        /// Modbus slave address
+        #[arg(short, long)]
+        slave: u8,
+
+        /// Starting address to write to
+        #[arg(short, long)]
+        address: u16,
+
+        /// Modbus function code (5=write_single_coil, 6=write_single_register, 15=write_multiple_coils, 16=write_multiple_registers)
+        #[arg(short, long, default_value = "6")]
+        function_code: u8,
+
+        /// Comma-separated values to write (booleans for coils, decimal or 0x-prefixed hex for registers)
+        #[arg(long, value_delimiter = ',')]
+        values: Vec<String>,
+
+        /// Timeout in milliseconds
+        #[arg(long, default_value = "1000")]
+        timeout: u64,
+    },
+    /// Bridge a Modbus serial device's registers to MQTT
+    Mqtt {
+        /// Serial port path (e.g., COM1, /dev/ttyUSB0)
+        #[arg(short, long)]
+        port: String,
+
+        /// Baud rate
+        #[arg(short, long, default_value = "9600")]
+        baud: u32,
+
+        /// Data bits (5, 6, 7, 8)
+        #[arg(long, default_value = "8")]
+        data_bits: u8,
+
+        /// Stop bits (1, 2)
+        #[arg(long, default_value = "1")]
+        stop_bits: u8,
+
+        /// Parity (none, odd, even)
+        #[arg(long, default_value = "none")]
+        parity: String,
+
+        /// Modbus slave address
+        #[arg(short, long)]
+        slave: u8,
+
+        /// Starting address to read from
+        #[arg(short, long)]
+        address: u16,
+
+        /// Number of registers to read
+        #[arg(short, long)]
+        count: u16,
+
+        /// Modbus function code (1=coils, 2=discrete_inputs, 3=holding_registers, 4=input_registers)
+        #[arg(short, long, default_value = "3")]
+        function_code: u8,
+
+        /// Timeout in milliseconds
+        #[arg(long, default_value = "1000")]
+        timeout: u64,
+
+        /// Decode holding/input registers as this type instead of raw u16 words
+        #[arg(long, value_enum)]
+        r#type: Option<RegisterType>,
+
+        /// Register order for multi-register types
+        #[arg(long, value_enum, default_value = "big")]
+        word_order: WordOrder,
+
+        /// Scale factor applied to decoded numeric values (value * scale + offset)
+        #[arg(long, default_value = "1")]
+        scale: Decimal,
+
+        /// Offset added after scaling
+        #[arg(long, default_value = "0")]
+        offset: Decimal,
+
+        /// MQTT broker URL, e.g. mqtt://localhost:1883/modbus
+        #[arg(long)]
+        mqtt_url: String,
+
+        /// Interval in milliseconds between polls published to MQTT
+        #[arg(long, default_value = "1000")]
+        poll: u64,
+    },
+    /// Bridge a Modbus TCP device's registers to MQTT
+    MqttTcp {
+        /// Device host name or IP address
+        #[arg(long)]
+        host: String,
+
+        /// Device port
+        #[arg(long, default_value = "502")]
+        port: u16,
+
+        /// Modbus slave address
+        #[arg(short, long)]
+        slave: u8,
+
+        /// Starting address to read from
+        #[arg(short, long)]
+        address: u16,
+
+        /// Number of registers to read
+        #[arg(short, long)]
+        count: u16,
+
+        /// Modbus function code (1=coils, 2=discrete_inputs, 3=holding_registers, 4=input_registers)
+        #[arg(short, long, default_value = "3")]
+        function_code: u8,
+
         /// Timeout in milliseconds
         #[arg(long, default_value = "1000")]
         timeout: u64,
+
+        /// Decode holding/input registers as this type instead of raw u16 words
+        #[arg(long, value_enum)]
+        r#type: Option<RegisterType>,
+
+        /// Register order for multi-register types
+        #[arg(long, value_enum, default_value = "big")]
+        word_order: WordOrder,
+
+        /// Scale factor applied to decoded numeric values (value * scale + offset)
+        #[arg(long, default_value = "1")]
+        scale: Decimal,
+
+        /// Offset added after scaling
+        #[arg(long, default_value = "0")]
+        offset: Decimal,
+
+        /// MQTT broker URL, e.g. mqtt://localhost:1883/modbus
+        #[arg(long)]
+        mqtt_url: String,
+
+        /// Interval in milliseconds between polls published to MQTT
+        #[arg(long, default_value = "1000")]
+        poll: u64,
+    },
+    /// Run a batch of named reads over one shared connection, defined in a JSON config file
+    Run {
+        /// Path to a JSON file describing the connection and the reads to perform
+        #[arg(short, long)]
+        config: PathBuf,
     },
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let cli = Cli::parse();
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let format = cli.format;
+
+    match cli.command {
+        Commands::ListPorts => list_ports().await?,
+        Commands::Read {
+            port,
+            baud,
+            data_bits,
+            stop_bits,
+            parity,
+            slave,
+            address,
+            count,
+            function_code,
+            timeout,
+            r#type,
+            word_order,
+            scale,
+            offset,
+            poll,
+            diff,
+        } => {
+            let decode = DecodeOptions { reg_type: r#type, word_order, scale, offset };
+            read_modbus_data(
+                &port, baud, data_bits, stop_bits, &parity, slave, address, count, function_code, timeout,
+                decode, poll, diff, format,
+            )
+            .await?;
+        }
+        Commands::ReadTcp {
+            host,
+            port,
+            slave,
+            address,
+            count,
+            function_code,
+            timeout,
+            r#type,
+            word_order,
+            scale,
+            offset,
+            poll,
+            diff,
+        } => {
+            let decode = DecodeOptions { reg_type: r#type, word_order, scale, offset };
+            read_modbus_data_tcp(
+                &host, port, slave, address, count, function_code, timeout, decode, poll, diff, format,
+            )
+            .await?;
+        }
+        Commands::Write {
+            port,
+            baud,
+            data_bits,
+            stop_bits,
+            parity,
+            slave,
+            address,
+            function_code,
+            values,
+            timeout,
+        } => {
+            write_modbus_data(
+                &port, baud, data_bits, stop_bits, &parity, slave, address, function_code, &values, timeout, format,
+            )
+            .await?;
+        }
+        Commands::WriteTcp {
+            host,
+            port,
+            slave,
+            address,
+            function_code,
+            values,
+            timeout,
+        } => {
+            write_modbus_data_tcp(&host, port, slave, address, function_code, &values, timeout, format).await?;
+        }
+        Commands::Mqtt {
+            port,
+            baud,
+            data_bits,
+            stop_bits,
+            parity,
+            slave,
+            address,
+            count,
+            function_code,
+            timeout,
+            r#type,
+            word_order,
+            scale,
+            offset,
+            mqtt_url,
+            poll,
+        } => {
+            let decode = DecodeOptions { reg_type: r#type, word_order, scale, offset };
+            mqtt_bridge(
+                &port, baud, data_bits, stop_bits, &parity, slave, address, count, function_code, timeout,
+                decode, &mqtt_url, poll,
+            )
+            .await?;
+        }
+        Commands::MqttTcp {
+            host,
+            port,
+            slave,
+            address,
+            count,
+            function_code,
+            timeout,
+            r#type,
+            word_order,
+            scale,
+            offset,
+            mqtt_url,
+            poll,
+        } => {
+            let decode = DecodeOptions { reg_type: r#type, word_order, scale, offset };
+            mqtt_bridge_tcp(
+                &host, port, slave, address, count, function_code, timeout, decode, &mqtt_url, poll,
+            )
+            .await?;
+        }
+        Commands::Run { config } => {
+            run_batch(&config, format).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn list_ports() -> Result<()> {
+    println!("Available serial ports:");
+    println!("{:-<60}", "");
+    
+    match tokio_serial::available_ports() {
+        Ok(ports) => {
+            if ports.is_empty() {
+                println!("No serial ports found.");
+            } else {
+                for (i, port) in ports.iter().enumerate() {
+                    println!("{}. Port: {}", i + 1, port.port_name);
+                    
+                    match &port.port_type {
+                        tokio_serial::SerialPortType::UsbPort(usb_info) => {
+                            println!("   Type: USB");
+                            if let Some(manufacturer) = &usb_info.manufacturer {
+                                println!("   Manufacturer: {}", manufacturer);
+                            }
+                            if let Some(product) = &usb_info.product {
+                                println!("   Product: {}", product);
+                            }
+                            if let Some(serial_number) = &usb_info.serial_number {
+                                println!("   Serial Number: {}", serial_number);
+                            }
+                            println!("   VID: {:04X}, PID: {:04X}", usb_info.vid, usb_info.pid);
+                        }
+                        tokio_serial::SerialPortType::BluetoothPort => {
+                            println!("   Type: Bluetooth");
+                        }
+                        tokio_serial::SerialPortType::PciPort => {
+                            println!("   Type: PCI");
+                        }
+                        tokio_serial::SerialPortType::Unknown => {
+                            println!("   Type: Unknown");
+                        }
+                    }
+                    println!();
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to list ports: {}", e);
+        }
+    }
+    
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn read_modbus_data(
+    port_name: &str,
+    baud_rate: u32,
+    data_bits: u8,
+    stop_bits: u8,
+    parity_str: &str,
+    slave_id: u8,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    timeout_ms: u64,
+    decode: DecodeOptions,
+    poll: Option<u64>,
+    diff: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    // Parse parity
+    let parity = match parity_str.to_lowercase().as_str() {
+        "none" => tokio_serial::Parity::None,
+        "odd" => tokio_serial::Parity::Odd,
+        "even" => tokio_serial::Parity::Even,
+        _ => {
+            eprintln!("Invalid parity. Use: none, odd, or even");
+            return Ok(());
+        }
+    };
+
+    // Parse data bits
+    let data_bits = match data_bits {
+        5 => tokio_serial::DataBits::Five,
+        6 => tokio_serial::DataBits::Six,
+        7 => tokio_serial::DataBits::Seven,
+        8 => tokio_serial::DataBits::Eight,
+        _ => {
+            eprintln!("Invalid data bits. Use: 5, 6, 7, or 8");
+            return Ok(());
+        }
+    };
+
+    // Parse stop bits
+    let stop_bits = match stop_bits {
+        1 => tokio_serial::StopBits::One,
+        2 => tokio_serial::StopBits::Two,
+        _ => {
+            eprintln!("Invalid stop bits. Use: 1 or 2");
+            return Ok(());
+        }
+    };
+
+    println!("Connecting to Modbus device:");
+    println!("  Port: {}", port_name);
+    println!("  Baud Rate: {}", baud_rate);
+    println!("  Data Bits: {:?}", data_bits);
+    println!("  Stop Bits: {:?}", stop_bits);
+    println!("  Parity: {:?}", parity);
+    println!("  Slave ID: {}", slave_id);
+    println!("  Function Code: {} (0x{:02X})", function_code, function_code);
+    println!("  Address Range: {} - {}", start_address, start_address + count - 1);
+    println!("  Timeout: {}ms", timeout_ms);
+    println!();
+
+    // Create serial port
+    let builder = tokio_serial::new(port_name, baud_rate)
+        .data_bits(data_bits)
+        .stop_bits(stop_bits)
+        .parity(parity)
+        .timeout(Duration::from_millis(timeout_ms));
+
+    let serial_stream = SerialStream::open(&builder)?;
+
+    // Create Modbus RTU context
+    let mut ctx = rtu::attach_slave(serial_stream, Slave(slave_id));
+
+    match poll {
+        Some(interval_ms) => {
+            run_poll_loop(&mut ctx, start_address, count, function_code, &decode, interval_ms, diff, format).await
+        }
+        None => execute_read(&mut ctx, start_address, count, function_code, &decode, format).await,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn read_modbus_data_tcp(
+    host: &str,
+    port: u16,
+    slave_id: u8,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    timeout_ms: u64,
+    decode: DecodeOptions,
+    poll: Option<u64>,
+    diff: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    println!("Connecting to Modbus TCP device:");
+    println!("  Host: {}", host);
+    println!("  Port: {}", port);
+    println!("  Slave ID: {}", slave_id);
+    println!("  Function Code: {} (0x{:02X})", function_code, function_code);
+    println!("  Address Range: {} - {}", start_address, start_address + count - 1);
+    println!("  Timeout: {}ms", timeout_ms);
+    println!();
+
+    let socket_addr: SocketAddr = format!("{}:{}", host, port).parse()?;
+    let mut ctx = tokio::time::timeout(
+        Duration::from_millis(timeout_ms),
+        tcp::connect_slave(socket_addr, Slave(slave_id)),
+    )
+    .await??;
+
+    match poll {
+        Some(interval_ms) => {
+            run_poll_loop(&mut ctx, start_address, count, function_code, &decode, interval_ms, diff, format).await
+        }
+        None => execute_read(&mut ctx, start_address, count, function_code, &decode, format).await,
+    }
+}
+
+async fn execute_read(
+    ctx: &mut Context,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    decode: &DecodeOptions,
+    format: OutputFormat,
+) -> Result<()> {
+    // Execute Modbus function based on function code
+    match function_code {
+        1 => {
+            // Read Coils (0x01)
+            let result = ctx.read_coils(start_address, count).await?;
+            match result {
+                Ok(coils) => {
+                    if let OutputFormat::Table = format {
+                        println!("Successfully read {} coils:", coils.len());
+                        display_coil_data(&coils, start_address);
+                    } else {
+                        output::print_coils(format, &output::coil_records(&coils, start_address))?;
+                    }
+                }
+                Err(e) => {
+                    handle_modbus_error(e);
+                }
+            }
+        }
+        2 => {
+            // Read Discrete Inputs (0x02)
+            let result = ctx.read_discrete_inputs(start_address, count).await?;
+            match result {
+                Ok(inputs) => {
+                    if let OutputFormat::Table = format {
+                        println!("Successfully read {} discrete inputs:", inputs.len());
+                        display_coil_data(&inputs, start_address);
+                    } else {
+                        output::print_coils(format, &output::coil_records(&inputs, start_address))?;
+                    }
+                }
+                Err(e) => {
+                    handle_modbus_error(e);
+                }
+            }
+        }
+        3 => {
+            // Read Holding Registers (0x03)
+            let result = ctx.read_holding_registers(start_address, count).await?;
+            match result {
+                Ok(registers) => {
+                    if let OutputFormat::Table = format {
+                        println!("Successfully read {} holding registers:", registers.len());
+                        display_register_data(&registers, start_address);
+                        print_decoded(&registers, start_address, decode, None)?;
+                    } else {
+                        print_registers_formatted(format, &registers, start_address, decode)?;
+                    }
+                }
+                Err(e) => {
+                    handle_modbus_error(e);
+                }
+            }
+        }
+        4 => {
+            // Read Input Registers (0x04)
+            let result = ctx.read_input_registers(start_address, count).await?;
+            match result {
+                Ok(registers) => {
+                    if let OutputFormat::Table = format {
+                        println!("Successfully read {} input registers:", registers.len());
+                        display_register_data(&registers, start_address);
+                        print_decoded(&registers, start_address, decode, None)?;
+                    } else {
+                        print_registers_formatted(format, &registers, start_address, decode)?;
+                    }
+                }
+                Err(e) => {
+                    handle_modbus_error(e);
+                }
+            }
+        }
+        _ => {
+            eprintln!("Unsupported function code: {}. Supported codes: 1 (coils), 2 (discrete inputs), 3 (holding registers), 4 (input registers)", function_code);
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Render holding/input registers as JSON or CSV, attaching decoded values when `--type` is set.
+fn print_registers_formatted(
+    format: OutputFormat,
+    registers: &[u16],
+    start_address: u16,
+    decode: &DecodeOptions,
+) -> Result<()> {
+    let decoded = match decode.reg_type {
+        Some(reg_type) => decode_registers(registers, start_address, reg_type, decode.word_order, decode.scale, decode.offset)?,
+        None => Vec::new(),
+    };
+    output::print_registers(format, &output::register_records(registers, start_address, &decoded))
+}
+
+/// Print decoded groups; with `last` set (`--diff` polling), only groups whose raw words changed.
+fn print_decoded(registers: &[u16], start_address: u16, decode: &DecodeOptions, last: Option<&[u16]>) -> Result<()> {
+    let Some(reg_type) = decode.reg_type else {
+        return Ok(());
+    };
+
+    let groups: Vec<DecodedGroup> =
+        decode_registers(registers, start_address, reg_type, decode.word_order, decode.scale, decode.offset)?
+            .into_iter()
+            .filter(|g| {
+                let i = (g.address - start_address) as usize;
+                last.and_then(|l| l.get(i..i + g.raw_words.len())) != Some(g.raw_words.as_slice())
+            })
+            .collect();
+    if groups.is_empty() {
+        return Ok(());
+    }
+
+    println!("{:-<80}", "");
+    println!("Decoded ({:?}, word-order: {:?}):", reg_type, decode.word_order);
+    for group in groups {
+        let raw_hex: Vec<String> = group.raw_words.iter().map(|w| format!("0x{:04X}", w)).collect();
+        println!(
+            "Address: {:5} | Raw: [{}] | Value: {}",
+            group.address,
+            raw_hex.join(", "),
+            group.value
+        );
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_poll_loop(
+    ctx: &mut Context,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    decode: &DecodeOptions,
+    interval_ms: u64,
+    diff: bool,
+    format: OutputFormat,
+) -> Result<()> {
+    if !matches!(function_code, 1 | 2 | 3 | 4) {
+        eprintln!("Unsupported function code: {}. Supported codes: 1 (coils), 2 (discrete inputs), 3 (holding registers), 4 (input registers)", function_code);
+        return Ok(());
+    }
+
+    let mut interval = tokio::time::interval(Duration::from_millis(interval_ms));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    let mut last_coils: Option<Vec<bool>> = None;
+    let mut last_registers: Option<Vec<u16>> = None;
+
+    println!("Polling every {}ms. Press Ctrl-C to stop.", interval_ms);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                match function_code {
+                    1 | 2 => {
+                        let transport_result = if function_code == 1 {
+                            ctx.read_coils(start_address, count).await
+                        } else {
+                            ctx.read_discrete_inputs(start_address, count).await
+                        };
+                        let result = match transport_result {
+                            Ok(result) => result,
+                            Err(e) => {
+                                eprintln!("Error reading from device: {}", e);
+                                continue;
+                            }
+                        };
+                        match result {
+                            Ok(coils) => {
+                                match format {
+                                    OutputFormat::Table if diff => {
+                                        print_coil_diff(last_coils.as_deref(), &coils, start_address);
+                                    }
+                                    OutputFormat::Table => {
+                                        println!("Successfully read {} coils:", coils.len());
+                                        display_coil_data(&coils, start_address);
+                                    }
+                                    _ => {
+                                        let records = changed_coil_records(last_coils.as_deref(), &coils, start_address, diff);
+                                        output::print_coils(format, &records)?;
+                                    }
+                                }
+                                last_coils = Some(coils);
+                            }
+                            Err(e) => handle_modbus_error(e),
+                        }
+                    }
+                    _ => {
+                        let transport_result = if function_code == 3 {
+                            ctx.read_holding_registers(start_address, count).await
+                        } else {
+                            ctx.read_input_registers(start_address, count).await
+                        };
+                        let result = match transport_result {
+                            Ok(result) => result,
+                            Err(e) => {
+                                eprintln!("Error reading from device: {}", e);
+                                continue;
+                            }
+                        };
+                        match result {
+                            Ok(registers) => {
+                                match format {
+                                    OutputFormat::Table if diff => {
+                                        print_register_diff(last_registers.as_deref(), &registers, start_address);
+                                    }
+                                    OutputFormat::Table => {
+                                        println!("Successfully read {} registers:", registers.len());
+                                        display_register_data(&registers, start_address);
+                                    }
+                                    _ => {
+                                        let decoded = match decode.reg_type {
+                                            Some(reg_type) => decode_registers(
+                                                &registers, start_address, reg_type, decode.word_order, decode.scale, decode.offset,
+                                            )?,
+                                            None => Vec::new(),
+                                        };
+                                        let records = changed_register_records(
+                                            last_registers.as_deref(), &registers, start_address, &decoded, diff,
+                                        );
+                                        output::print_registers(format, &records)?;
+                                    }
+                                }
+                                if let OutputFormat::Table = format {
+                                    let previous = if diff { last_registers.as_deref() } else { None };
+                                    print_decoded(&registers, start_address, decode, previous)?;
+                                }
+                                last_registers = Some(registers);
+                            }
+                            Err(e) => handle_modbus_error(e),
+                        }
+                    }
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                println!();
+                println!("Stopping poll.");
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn print_coil_diff(last: Option<&[bool]>, coils: &[bool], start_address: u16) {
+    for (i, &value) in coils.iter().enumerate() {
+        if last.and_then(|l| l.get(i)) == Some(&value) {
+            continue;
+        }
+        let addr = start_address + i as u16;
+        println!(
+            "Address: {:5} (0x{:04X}) | Value: {:5} | State: {}",
+            addr, addr, if value { 1 } else { 0 }, if value { "ON" } else { "OFF" }
+        );
+    }
+}
+
+fn print_register_diff(last: Option<&[u16]>, registers: &[u16], start_address: u16) {
+    for (i, &value) in registers.iter().enumerate() {
+        if last.and_then(|l| l.get(i)) == Some(&value) {
+            continue;
+        }
+        let addr = start_address + i as u16;
+        println!(
+            "Address: {:5} (0x{:04X}) | Value: {:5} (0x{:04X}) | Binary: {:016b}",
+            addr, addr, value, value, value
+        );
+    }
+}
+
+/// Coil records for JSON/CSV poll output, filtered down to changed rows when `diff` is set.
+fn changed_coil_records(
+    last: Option<&[bool]>,
+    coils: &[bool],
+    start_address: u16,
+    diff: bool,
+) -> Vec<output::CoilRecord> {
+    output::coil_records(coils, start_address)
+        .into_iter()
+        .enumerate()
+        .filter(|(i, r)| !diff || last.and_then(|l| l.get(*i)) != Some(&r.value))
+        .map(|(_, r)| r)
+        .collect()
+}
+
+/// Register records for JSON/CSV poll output, filtered down to changed rows when `diff` is set.
+fn changed_register_records(
+    last: Option<&[u16]>,
+    registers: &[u16],
+    start_address: u16,
+    decoded: &[DecodedGroup],
+    diff: bool,
+) -> Vec<output::RegisterRecord> {
+    output::register_records(registers, start_address, decoded)
+        .into_iter()
+        .enumerate()
+        .filter(|(i, r)| !diff || last.and_then(|l| l.get(*i)) != Some(&r.value))
+        .map(|(_, r)| r)
+        .collect()
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_bridge(
+    port_name: &str,
+    baud_rate: u32,
+    data_bits: u8,
+    stop_bits: u8,
+    parity_str: &str,
+    slave_id: u8,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    timeout_ms: u64,
+    decode: DecodeOptions,
+    mqtt_url: &str,
+    poll_interval_ms: u64,
+) -> Result<()> {
+    // Parse parity
+    let parity = match parity_str.to_lowercase().as_str() {
+        "none" => tokio_serial::Parity::None,
+        "odd" => tokio_serial::Parity::Odd,
+        "even" => tokio_serial::Parity::Even,
+        _ => {
+            eprintln!("Invalid parity. Use: none, odd, or even");
+            return Ok(());
+        }
+    };
+
+    // Parse data bits
+    let data_bits = match data_bits {
+        5 => tokio_serial::DataBits::Five,
+        6 => tokio_serial::DataBits::Six,
+        7 => tokio_serial::DataBits::Seven,
+        8 => tokio_serial::DataBits::Eight,
+        _ => {
+            eprintln!("Invalid data bits. Use: 5, 6, 7, or 8");
+            return Ok(());
+        }
+    };
+
+    // Parse stop bits
+    let stop_bits = match stop_bits {
+        1 => tokio_serial::StopBits::One,
+        2 => tokio_serial::StopBits::Two,
+        _ => {
+            eprintln!("Invalid stop bits. Use: 1 or 2");
+            return Ok(());
+        }
+    };
+
+    println!("Connecting to Modbus device:");
+    println!("  Port: {}", port_name);
+    println!("  Baud Rate: {}", baud_rate);
+    println!("  Data Bits: {:?}", data_bits);
+    println!("  Stop Bits: {:?}", stop_bits);
+    println!("  Parity: {:?}", parity);
+    println!("  Slave ID: {}", slave_id);
+    println!("  Function Code: {} (0x{:02X})", function_code, function_code);
+    println!("  Address Range: {} - {}", start_address, start_address + count - 1);
+    println!("  Timeout: {}ms", timeout_ms);
+    println!();
+
+    let builder = tokio_serial::new(port_name, baud_rate)
+        .data_bits(data_bits)
+        .stop_bits(stop_bits)
+        .parity(parity)
+        .timeout(Duration::from_millis(timeout_ms));
+
+    let serial_stream = SerialStream::open(&builder)?;
+
+    let mut ctx = rtu::attach_slave(serial_stream, Slave(slave_id));
+
+    run_mqtt_loop(&mut ctx, slave_id, start_address, count, function_code, &decode, mqtt_url, poll_interval_ms).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_bridge_tcp(
+    host: &str,
+    port: u16,
+    slave_id: u8,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    timeout_ms: u64,
+    decode: DecodeOptions,
+    mqtt_url: &str,
+    poll_interval_ms: u64,
+) -> Result<()> {
+    println!("Connecting to Modbus TCP device:");
+    println!("  Host: {}", host);
+    println!("  Port: {}", port);
+    println!("  Slave ID: {}", slave_id);
+    println!("  Function Code: {} (0x{:02X})", function_code, function_code);
+    println!("  Address Range: {} - {}", start_address, start_address + count - 1);
+    println!("  Timeout: {}ms", timeout_ms);
+    println!();
 
-    match cli.command {
-        Commands::ListPorts => list_ports().await?,
-        Commands::Read {
-            port,
-            baud,
-            data_bits,
-            stop_bits,
-            parity,
-            slave,
-            address,
-            count,
-            function_code,
-            timeout,
-        } => {
-            read_modbus_data(
-                &port, baud, data_bits, stop_bits, &parity, slave, address, count, function_code, timeout,
-            )
-            .await?;
+    let socket_addr: SocketAddr = format!("{}:{}", host, port).parse()?;
+    let mut ctx = tokio::time::timeout(
+        Duration::from_millis(timeout_ms),
+        tcp::connect_slave(socket_addr, Slave(slave_id)),
+    )
+    .await??;
+
+    run_mqtt_loop(&mut ctx, slave_id, start_address, count, function_code, &decode, mqtt_url, poll_interval_ms).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn run_mqtt_loop(
+    ctx: &mut Context,
+    slave_id: u8,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    decode: &DecodeOptions,
+    mqtt_url: &str,
+    poll_interval_ms: u64,
+) -> Result<()> {
+    if !matches!(function_code, 1 | 2 | 3 | 4) {
+        eprintln!("Unsupported function code: {}. Supported codes: 1 (coils), 2 (discrete inputs), 3 (holding registers), 4 (input registers)", function_code);
+        return Ok(());
+    }
+
+    let target = mqtt::parse_mqtt_url(mqtt_url)?;
+    let client_id = format!("modbus-cli-{}", slave_id);
+
+    println!(
+        "Bridging slave {} address {}..{} to {}:{} (prefix '{}') every {}ms. Press Ctrl-C to stop.",
+        slave_id,
+        start_address,
+        start_address + count - 1,
+        target.host,
+        target.port,
+        target.prefix,
+        poll_interval_ms,
+    );
+
+    let (client, mut eventloop) = mqtt::connect(&client_id, &target).await?;
+    let eventloop_task = tokio::spawn(async move {
+        loop {
+            // `poll()` must be called in an unconditional loop: rumqttc reconnects
+            // internally on the next call after an error, so we keep going rather
+            // than abandoning the connection on the first transient failure.
+            if let Err(e) = eventloop.poll().await {
+                eprintln!("MQTT connection error: {}. Retrying...", e);
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        }
+    });
+
+    let mut interval = tokio::time::interval(Duration::from_millis(poll_interval_ms));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                if let Err(e) = publish_once(ctx, &client, &target, slave_id, start_address, count, function_code, decode).await {
+                    eprintln!("Error publishing to MQTT: {}", e);
+                }
+            }
+            _ = tokio::signal::ctrl_c() => {
+                println!();
+                println!("Stopping MQTT bridge.");
+                break;
+            }
         }
     }
 
+    eventloop_task.abort();
     Ok(())
 }
 
-async fn list_ports() -> Result<()> {
-    println!("Available serial ports:");
-    println!("{:-<60}", "");
-    
-    match tokio_serial::available_ports() {
-        Ok(ports) => {
-            if ports.is_empty() {
-                println!("No serial ports found.");
+#[allow(clippy::too_many_arguments)]
+async fn publish_once(
+    ctx: &mut Context,
+    client: &rumqttc::AsyncClient,
+    target: &mqtt::MqttTarget,
+    slave_id: u8,
+    start_address: u16,
+    count: u16,
+    function_code: u8,
+    decode: &DecodeOptions,
+) -> Result<()> {
+    match function_code {
+        1 | 2 => {
+            let result = if function_code == 1 {
+                ctx.read_coils(start_address, count).await?
             } else {
-                for (i, port) in ports.iter().enumerate() {
-                    println!("{}. Port: {}", i + 1, port.port_name);
-                    
-                    match &port.port_type {
-                        tokio_serial::SerialPortType::UsbPort(usb_info) => {
-                            println!("   Type: USB");
-                            if let Some(manufacturer) = &usb_info.manufacturer {
-                                println!("   Manufacturer: {}", manufacturer);
-                            }
-                            if let Some(product) = &usb_info.product {
-                                println!("   Product: {}", product);
-                            }
-                            if let Some(serial_number) = &usb_info.serial_number {
-                                println!("   Serial Number: {}", serial_number);
-                            }
-                            println!("   VID: {:04X}, PID: {:04X}", usb_info.vid, usb_info.pid);
-                        }
-                        tokio_serial::SerialPortType::BluetoothPort => {
-                            println!("   Type: Bluetooth");
-                        }
-                        tokio_serial::SerialPortType::PciPort => {
-                            println!("   Type: PCI");
+                ctx.read_discrete_inputs(start_address, count).await?
+            };
+            match result {
+                Ok(coils) => {
+                    for (i, value) in coils.iter().enumerate() {
+                        let address = start_address + i as u16;
+                        let payload = serde_json::json!({ "value": value });
+                        mqtt::publish_value(client, &target.prefix, slave_id, address, &payload).await?;
+                    }
+                }
+                Err(e) => handle_modbus_error(e),
+            }
+        }
+        _ => {
+            let result = if function_code == 3 {
+                ctx.read_holding_registers(start_address, count).await?
+            } else {
+                ctx.read_input_registers(start_address, count).await?
+            };
+            match result {
+                Ok(registers) => {
+                    if let Some(reg_type) = decode.reg_type {
+                        let groups = decode_registers(
+                            &registers, start_address, reg_type, decode.word_order, decode.scale, decode.offset,
+                        )?;
+                        for group in groups {
+                            let value = match group.numeric {
+                                Some(numeric) => serde_json::json!({ "value": numeric.to_f64() }),
+                                None => serde_json::json!({ "value": group.value }),
+                            };
+                            mqtt::publish_value(client, &target.prefix, slave_id, group.address, &value).await?;
                         }
-                        tokio_serial::SerialPortType::Unknown => {
-                            println!("   Type: Unknown");
+                    } else {
+                        for (i, value) in registers.iter().enumerate() {
+                            let address = start_address + i as u16;
+                            let payload = serde_json::json!({ "value": value });
+                            mqtt::publish_value(client, &target.prefix, slave_id, address, &payload).await?;
                         }
                     }
-                    println!();
                 }
+                Err(e) => handle_modbus_error(e),
             }
         }
-        Err(e) => {
-            eprintln!("Failed to list ports: {}", e);
-        }
     }
-    
+
     Ok(())
 }
 
-async fn read_modbus_data(
+#[allow(clippy::too_many_arguments)]
+async fn write_modbus_data(
     port_name: &str,
     baud_rate: u32,
     data_bits: u8,
@@ -144,9 +1222,10 @@ async fn read_modbus_data(
     parity_str: &str,
     slave_id: u8,
     start_address: u16,
-    count: u16,
     function_code: u8,
+    values: &[String],
     timeout_ms: u64,
+    format: OutputFormat,
 ) -> Result<()> {
     // Parse parity
     let parity = match parity_str.to_lowercase().as_str() {
@@ -189,11 +1268,9 @@ async fn read_modbus_data(
     println!("  Parity: {:?}", parity);
     println!("  Slave ID: {}", slave_id);
     println!("  Function Code: {} (0x{:02X})", function_code, function_code);
-    println!("  Address Range: {} - {}", start_address, start_address + count - 1);
     println!("  Timeout: {}ms", timeout_ms);
     println!();
 
-    // Create serial port
     let builder = tokio_serial::new(port_name, baud_rate)
         .data_bits(data_bits)
         .stop_bits(stop_bits)
@@ -201,66 +1278,116 @@ async fn read_modbus_data(
         .timeout(Duration::from_millis(timeout_ms));
 
     let serial_stream = SerialStream::open(&builder)?;
-    
-    // Create Modbus RTU context
+
     let mut ctx = rtu::attach_slave(serial_stream, Slave(slave_id));
 
-    // Execute Modbus function based on function code
+    execute_write(&mut ctx, start_address, function_code, values, format).await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn write_modbus_data_tcp(
+    host: &str,
+    port: u16,
+    slave_id: u8,
+    start_address: u16,
+    function_code: u8,
+    values: &[String],
+    timeout_ms: u64,
+    format: OutputFormat,
+) -> Result<()> {
+    println!("Connecting to Modbus TCP device:");
+    println!("  Host: {}", host);
+    println!("  Port: {}", port);
+    println!("  Slave ID: {}", slave_id);
+    println!("  Function Code: {} (0x{:02X})", function_code, function_code);
+    println!("  Timeout: {}ms", timeout_ms);
+    println!();
+
+    let socket_addr: SocketAddr = format!("{}:{}", host, port).parse()?;
+    let mut ctx = tokio::time::timeout(
+        Duration::from_millis(timeout_ms),
+        tcp::connect_slave(socket_addr, Slave(slave_id)),
+    )
+    .await??;
+
+    execute_write(&mut ctx, start_address, function_code, values, format).await
+}
+
+async fn execute_write(
+    ctx: &mut Context,
+    start_address: u16,
+    function_code: u8,
+    values: &[String],
+    format: OutputFormat,
+) -> Result<()> {
     match function_code {
-        1 => {
-            // Read Coils (0x01)
-            let result = ctx.read_coils(start_address, count).await?;
-            match result {
-                Ok(coils) => {
-                    println!("Successfully read {} coils:", coils.len());
-                    display_coil_data(&coils, start_address);
-                }
-                Err(e) => {
-                    handle_modbus_error(e);
+        5 => {
+            // Write Single Coil (0x05)
+            let coils = parse_coil_values(values)?;
+            if coils.len() != 1 {
+                eprintln!("write_single_coil expects exactly one value in --values");
+                return Ok(());
+            }
+            match ctx.write_single_coil(start_address, coils[0]).await? {
+                Ok(()) => {
+                    println!("Successfully wrote coil at address {}", start_address);
+                    verify_coils(ctx, start_address, 1, format).await?;
                 }
+                Err(e) => handle_modbus_error(e),
             }
         }
-        2 => {
-            // Read Discrete Inputs (0x02)
-            let result = ctx.read_discrete_inputs(start_address, count).await?;
-            match result {
-                Ok(inputs) => {
-                    println!("Successfully read {} discrete inputs:", inputs.len());
-                    display_coil_data(&inputs, start_address);
-                }
-                Err(e) => {
-                    handle_modbus_error(e);
+        6 => {
+            // Write Single Register (0x06)
+            let registers = parse_register_values(values)?;
+            if registers.len() != 1 {
+                eprintln!("write_single_register expects exactly one value in --values");
+                return Ok(());
+            }
+            match ctx.write_single_register(start_address, registers[0]).await? {
+                Ok(()) => {
+                    println!("Successfully wrote register at address {}", start_address);
+                    verify_registers(ctx, start_address, 1, format).await?;
                 }
+                Err(e) => handle_modbus_error(e),
             }
         }
-        3 => {
-            // Read Holding Registers (0x03)
-            let result = ctx.read_holding_registers(start_address, count).await?;
-            match result {
-                Ok(registers) => {
-                    println!("Successfully read {} holding registers:", registers.len());
-                    display_register_data(&registers, start_address);
-                }
-                Err(e) => {
-                    handle_modbus_error(e);
+        15 => {
+            // Write Multiple Coils (0x0F)
+            let coils = parse_coil_values(values)?;
+            if coils.is_empty() {
+                eprintln!("write_multiple_coils expects at least one value in --values");
+                return Ok(());
+            }
+            let count = coils.len() as u16;
+            match ctx.write_multiple_coils(start_address, &coils).await? {
+                Ok(()) => {
+                    println!("Successfully wrote {} coils starting at address {}", count, start_address);
+                    verify_coils(ctx, start_address, count, format).await?;
                 }
+                Err(e) => handle_modbus_error(e),
             }
         }
-        4 => {
-            // Read Input Registers (0x04)
-            let result = ctx.read_input_registers(start_address, count).await?;
-            match result {
-                Ok(registers) => {
-                    println!("Successfully read {} input registers:", registers.len());
-                    display_register_data(&registers, start_address);
-                }
-                Err(e) => {
-                    handle_modbus_error(e);
+        16 => {
+            // Write Multiple Registers (0x10)
+            let registers = parse_register_values(values)?;
+            if registers.is_empty() {
+                eprintln!("write_multiple_registers expects at least one value in --values");
+                return Ok(());
+            }
+            let count = registers.len() as u16;
+            match ctx.write_multiple_registers(start_address, &registers).await? {
+                Ok(()) => {
+                    println!("Successfully wrote {} registers starting at address {}", count, start_address);
+                    verify_registers(ctx, start_address, count, format).await?;
                 }
+                Err(e) => handle_modbus_error(e),
             }
         }
         _ => {
-            eprintln!("Unsupported function code: {}. Supported codes: 1 (coils), 2 (discrete inputs), 3 (holding registers), 4 (input registers)", function_code);
+            eprintln!(
+                "Unsupported function code: {}. Supported codes: 5 (write single coil), 6 (write single register), 15 (write multiple coils), 16 (write multiple registers)",
+                function_code
+            );
             return Ok(());
         }
     }
@@ -268,6 +1395,156 @@ async fn read_modbus_data(
     Ok(())
 }
 
+async fn verify_coils(ctx: &mut Context, start_address: u16, count: u16, format: OutputFormat) -> Result<()> {
+    match ctx.read_coils(start_address, count).await? {
+        Ok(coils) => {
+            if let OutputFormat::Table = format {
+                println!("Read-back verification:");
+                display_coil_data(&coils, start_address);
+            } else {
+                output::print_coils(format, &output::coil_records(&coils, start_address))?;
+            }
+        }
+        Err(e) => handle_modbus_error(e),
+    }
+    Ok(())
+}
+
+async fn verify_registers(ctx: &mut Context, start_address: u16, count: u16, format: OutputFormat) -> Result<()> {
+    match ctx.read_holding_registers(start_address, count).await? {
+        Ok(registers) => {
+            if let OutputFormat::Table = format {
+                println!("Read-back verification:");
+                display_register_data(&registers, start_address);
+            } else {
+                output::print_registers(format, &output::register_records(&registers, start_address, &[]))?;
+            }
+        }
+        Err(e) => handle_modbus_error(e),
+    }
+    Ok(())
+}
+
+async fn run_batch(config_path: &std::path::Path, format: OutputFormat) -> Result<()> {
+    let batch = config::load(config_path)?;
+
+    let mut ctx = match &batch.transport {
+        config::TransportConfig::Serial { port, baud, data_bits, stop_bits, parity, timeout } => {
+            connect_serial_ctx(port, *baud, *data_bits, *stop_bits, parity, *timeout).await?
+        }
+        config::TransportConfig::Tcp { host, port, timeout } => connect_tcp_ctx(host, *port, *timeout).await?,
+    };
+
+    for read in &batch.reads {
+        println!("{:-<80}", "");
+        println!(
+            "[{}] slave {} | function {} | address {} | count {}",
+            read.name, read.slave, read.function_code, read.address, read.count
+        );
+
+        ctx.set_slave(Slave(read.slave));
+
+        let decode = DecodeOptions {
+            reg_type: read.reg_type,
+            word_order: read.word_order,
+            scale: read.scale,
+            offset: read.offset,
+        };
+
+        if let Err(e) = execute_read(&mut ctx, read.address, read.count, read.function_code, &decode, format).await {
+            eprintln!("[{}] failed: {}", read.name, e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn connect_serial_ctx(
+    port_name: &str,
+    baud_rate: u32,
+    data_bits: u8,
+    stop_bits: u8,
+    parity_str: &str,
+    timeout_ms: u64,
+) -> Result<Context> {
+    let parity = match parity_str.to_lowercase().as_str() {
+        "none" => tokio_serial::Parity::None,
+        "odd" => tokio_serial::Parity::Odd,
+        "even" => tokio_serial::Parity::Even,
+        other => anyhow::bail!("Invalid parity '{}'. Use: none, odd, or even", other),
+    };
+    let data_bits = match data_bits {
+        5 => tokio_serial::DataBits::Five,
+        6 => tokio_serial::DataBits::Six,
+        7 => tokio_serial::DataBits::Seven,
+        8 => tokio_serial::DataBits::Eight,
+        other => anyhow::bail!("Invalid data bits {}. Use: 5, 6, 7, or 8", other),
+    };
+    let stop_bits = match stop_bits {
+        1 => tokio_serial::StopBits::One,
+        2 => tokio_serial::StopBits::Two,
+        other => anyhow::bail!("Invalid stop bits {}. Use: 1 or 2", other),
+    };
+
+    println!("Connecting to Modbus device:");
+    println!("  Port: {}", port_name);
+    println!("  Baud Rate: {}", baud_rate);
+    println!();
+
+    let builder = tokio_serial::new(port_name, baud_rate)
+        .data_bits(data_bits)
+        .stop_bits(stop_bits)
+        .parity(parity)
+        .timeout(Duration::from_millis(timeout_ms));
+
+    let serial_stream = SerialStream::open(&builder)?;
+
+    // The per-read slave id is set on the context before each read via `set_slave`.
+    Ok(rtu::attach_slave(serial_stream, Slave(0)))
+}
+
+async fn connect_tcp_ctx(host: &str, port: u16, timeout_ms: u64) -> Result<Context> {
+    println!("Connecting to Modbus TCP device:");
+    println!("  Host: {}", host);
+    println!("  Port: {}", port);
+    println!();
+
+    let socket_addr: SocketAddr = format!("{}:{}", host, port).parse()?;
+    let ctx = tokio::time::timeout(
+        Duration::from_millis(timeout_ms),
+        tcp::connect_slave(socket_addr, Slave(0)),
+    )
+    .await??;
+
+    // The per-read slave id is set on the context before each read via `set_slave`.
+    Ok(ctx)
+}
+
+fn parse_coil_values(values: &[String]) -> Result<Vec<bool>> {
+    values
+        .iter()
+        .map(|v| match v.trim().to_lowercase().as_str() {
+            "1" | "true" | "on" => Ok(true),
+            "0" | "false" | "off" => Ok(false),
+            other => Err(anyhow::anyhow!("Invalid coil value '{}'. Use true/false, 1/0, or on/off", other)),
+        })
+        .collect()
+}
+
+fn parse_register_values(values: &[String]) -> Result<Vec<u16>> {
+    values
+        .iter()
+        .map(|v| {
+            let v = v.trim();
+            if let Some(hex) = v.strip_prefix("0x").or_else(|| v.strip_prefix("0X")) {
+                u16::from_str_radix(hex, 16).map_err(|e| anyhow::anyhow!("Invalid hex register value '{}': {}", v, e))
+            } else {
+                v.parse::<u16>().map_err(|e| anyhow::anyhow!("Invalid register value '{}': {}", v, e))
+            }
+        })
+        .collect()
+}
+
 fn display_coil_data(coils: &[bool], start_address: u16) {
     println!("{:-<80}", "");
     
@@ -376,4 +1653,33 @@ fn handle_modbus_error(e: tokio_modbus::Exception) {
             eprintln!("  - Check the device documentation for error details");
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_coil_values_accepts_aliases() {
+        let values = vec!["1".to_string(), "false".to_string(), "ON".to_string()];
+        assert_eq!(parse_coil_values(&values).unwrap(), vec![true, false, true]);
+    }
+
+    #[test]
+    fn parse_coil_values_rejects_unknown_token() {
+        let values = vec!["maybe".to_string()];
+        assert!(parse_coil_values(&values).is_err());
+    }
+
+    #[test]
+    fn parse_register_values_accepts_decimal_and_hex() {
+        let values = vec!["42".to_string(), "0x2A".to_string()];
+        assert_eq!(parse_register_values(&values).unwrap(), vec![42, 42]);
+    }
+
+    #[test]
+    fn parse_register_values_rejects_out_of_range() {
+        let values = vec!["70000".to_string()];
+        assert!(parse_register_values(&values).is_err());
+    }
 }
\ No newline at end of file
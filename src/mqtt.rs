@@ -0,0 +1,113 @@
+use anyhow::{anyhow, Result};
+use rumqttc::{AsyncClient, EventLoop, LastWill, MqttOptions, QoS};
+use std::time::Duration;
+
+/// Host, port and topic prefix parsed out of a `--mqtt-url` of the form
+/// `mqtt://host:1883/prefix`.
+pub struct MqttTarget {
+    pub host: String,
+    pub port: u16,
+    pub prefix: String,
+}
+
+pub fn parse_mqtt_url(url: &str) -> Result<MqttTarget> {
+    let rest = url
+        .strip_prefix("mqtt://")
+        .ok_or_else(|| anyhow!("--mqtt-url must start with mqtt://, got '{}'", url))?;
+    let (authority, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+    let (host, port) = match authority.split_once(':') {
+        Some((host, port)) => (
+            host.to_string(),
+            port.parse::<u16>()
+                .map_err(|_| anyhow!("invalid MQTT port '{}' in --mqtt-url", port))?,
+        ),
+        None => (authority.to_string(), 1883),
+    };
+    if host.is_empty() {
+        return Err(anyhow!("--mqtt-url is missing a host"));
+    }
+
+    Ok(MqttTarget {
+        host,
+        port,
+        prefix: prefix.trim_end_matches('/').to_string(),
+    })
+}
+
+/// Connect to the broker with a retained LastWill of "offline" on `<prefix>/status`,
+/// then immediately publish "online" to the same topic.
+pub async fn connect(client_id: &str, target: &MqttTarget) -> Result<(AsyncClient, EventLoop)> {
+    let status_topic = format!("{}/status", target.prefix);
+
+    let mut options = MqttOptions::new(client_id, target.host.clone(), target.port);
+    options.set_keep_alive(Duration::from_secs(30));
+    options.set_last_will(LastWill::new(status_topic.clone(), "offline", QoS::AtLeastOnce, true));
+
+    let (client, eventloop) = AsyncClient::new(options, 16);
+    client
+        .publish(status_topic, QoS::AtLeastOnce, true, "online")
+        .await?;
+
+    Ok((client, eventloop))
+}
+
+/// Publish one register/coil value as JSON to `<prefix>/<slave>/<address>`.
+pub async fn publish_value(
+    client: &AsyncClient,
+    prefix: &str,
+    slave_id: u8,
+    address: u16,
+    value: &serde_json::Value,
+) -> Result<()> {
+    let topic = format!("{}/{}/{}", prefix, slave_id, address);
+    let payload = serde_json::to_vec(value)?;
+    client.publish(topic, QoS::AtLeastOnce, false, payload).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_host_port_and_prefix() {
+        let target = parse_mqtt_url("mqtt://localhost:1883/modbus").unwrap();
+        assert_eq!(target.host, "localhost");
+        assert_eq!(target.port, 1883);
+        assert_eq!(target.prefix, "modbus");
+    }
+
+    #[test]
+    fn defaults_port_when_missing() {
+        let target = parse_mqtt_url("mqtt://broker.local/modbus").unwrap();
+        assert_eq!(target.host, "broker.local");
+        assert_eq!(target.port, 1883);
+    }
+
+    #[test]
+    fn defaults_prefix_when_missing() {
+        let target = parse_mqtt_url("mqtt://localhost:1883").unwrap();
+        assert_eq!(target.prefix, "");
+    }
+
+    #[test]
+    fn strips_trailing_slash_from_prefix() {
+        let target = parse_mqtt_url("mqtt://localhost:1883/modbus/").unwrap();
+        assert_eq!(target.prefix, "modbus");
+    }
+
+    #[test]
+    fn rejects_missing_scheme() {
+        assert!(parse_mqtt_url("localhost:1883/modbus").is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_port() {
+        assert!(parse_mqtt_url("mqtt://localhost:notaport/modbus").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_host() {
+        assert!(parse_mqtt_url("mqtt://:1883/modbus").is_err());
+    }
+}
@@ -0,0 +1,86 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::decode::DecodedGroup;
+
+/// How read results are rendered: the original human-readable tables, or a
+/// machine-readable format scripts can consume.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Serialize)]
+pub struct CoilRecord {
+    pub address: u16,
+    pub value: bool,
+}
+
+#[derive(Serialize)]
+pub struct RegisterRecord {
+    pub address: u16,
+    pub hex: String,
+    pub value: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<String>,
+}
+
+pub fn coil_records(coils: &[bool], start_address: u16) -> Vec<CoilRecord> {
+    coils
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| CoilRecord { address: start_address + i as u16, value })
+        .collect()
+}
+
+pub fn register_records(registers: &[u16], start_address: u16, decoded: &[DecodedGroup]) -> Vec<RegisterRecord> {
+    let decoded_by_address: HashMap<u16, &str> =
+        decoded.iter().map(|g| (g.address, g.value.as_str())).collect();
+
+    registers
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| {
+            let address = start_address + i as u16;
+            RegisterRecord {
+                address,
+                hex: format!("0x{:04X}", value),
+                value,
+                decoded: decoded_by_address.get(&address).map(|s| s.to_string()),
+            }
+        })
+        .collect()
+}
+
+pub fn print_coils(format: OutputFormat, records: &[CoilRecord]) -> Result<()> {
+    match format {
+        OutputFormat::Table => unreachable!("table output is rendered by the caller"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(records)?),
+        OutputFormat::Csv => {
+            println!("address,value");
+            for r in records {
+                println!("{},{}", r.address, r.value);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print_registers(format: OutputFormat, records: &[RegisterRecord]) -> Result<()> {
+    match format {
+        OutputFormat::Table => unreachable!("table output is rendered by the caller"),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(records)?),
+        OutputFormat::Csv => {
+            println!("address,value");
+            for r in records {
+                println!("{},{}", r.address, r.value);
+            }
+        }
+    }
+    Ok(())
+}